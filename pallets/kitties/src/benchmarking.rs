@@ -0,0 +1,68 @@
+//! Benchmarking setup for pallet-kitties
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Kitties;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::Currency;
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	let balance = T::Currency::minimum_balance().saturating_mul(1_000u32.into());
+	T::Currency::make_free_balance_be(&caller, balance);
+	caller
+}
+
+fn mint_kitty<T: Config>(owner: &T::AccountId, gender: Option<Gender>) -> T::Hash {
+	Pallet::<T>::mint(owner, None, gender, None).expect("mint should not fail in benchmarks")
+}
+
+benchmarks! {
+	create_kitty {
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller))
+
+	set_price {
+		let caller = funded_account::<T>("caller", 0);
+		let kitty_id = mint_kitty::<T>(&caller, None);
+	}: _(RawOrigin::Signed(caller), kitty_id, Some(1_000u32.into()))
+
+	transfer {
+		let caller = funded_account::<T>("caller", 0);
+		let recipient = funded_account::<T>("recipient", 1);
+		let kitty_id = mint_kitty::<T>(&caller, None);
+		// Fill the sender's `KittiesOwned` to `MaxKittyOwned` so the benchmark covers the
+		// worst-case removal and index bookkeeping path.
+		// Each filler Kitty gets its own DNA: Kitties minted with random DNA in the same block
+		// would all hash to the same id.
+		for i in 1..T::MaxKittyOwned::get() {
+			let mut dna = [0u8; 16];
+			dna[..4].copy_from_slice(&i.to_le_bytes());
+			Pallet::<T>::mint(&caller, Some(dna), None, None)
+				.expect("mint should not fail in benchmarks");
+		}
+	}: _(RawOrigin::Signed(caller), recipient, kitty_id)
+
+	buy_kitty {
+		let seller = funded_account::<T>("seller", 0);
+		let buyer = funded_account::<T>("buyer", 1);
+		let kitty_id = mint_kitty::<T>(&seller, None);
+		Kitties::<T>::set_price(RawOrigin::Signed(seller).into(), kitty_id, Some(1_000u32.into()))?;
+	}: _(RawOrigin::Signed(buyer), kitty_id, 1_000u32.into())
+
+	breed_kitty {
+		let caller = funded_account::<T>("caller", 0);
+		let parent1 = mint_kitty::<T>(&caller, Some(Gender::Male));
+		let parent2 = mint_kitty::<T>(&caller, Some(Gender::Female));
+	}: _(RawOrigin::Signed(caller), parent1, parent2)
+
+	name_kitty {
+		let caller = funded_account::<T>("caller", 0);
+		let kitty_id = mint_kitty::<T>(&caller, None);
+		let name = sp_std::vec![b'a'; T::MaxNameLength::get() as usize];
+	}: _(RawOrigin::Signed(caller), kitty_id, name)
+}
@@ -2,6 +2,10 @@
 
 pub use pallet::*;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::{
@@ -14,6 +18,9 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 	use scale_info::TypeInfo;
 	use sp_io::hashing::blake2_128;
+	use sp_std::marker::PhantomData;
+
+	use crate::weights::WeightInfo;
 
 	#[cfg(feature = "std")]
 	use frame_support::serde::{Deserialize, Serialize};
@@ -31,6 +38,10 @@ pub mod pallet {
 		pub gender: Gender,
 		pub owner: AccountOf<T>,
 		pub name: Option<BoundedVec<u8, T::MaxNameLength>>,
+		/// How many generations removed this Kitty is from a genesis/created Kitty.
+		pub generation: u64,
+		/// The two parents this Kitty was bred from, or `None` if it was created directly.
+		pub parents: Option<(T::Hash, T::Hash)>,
 	}
 
 	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -40,6 +51,37 @@ pub mod pallet {
 		Female,
 	}
 
+	/// A Kitty lifecycle event relayed onto an [`OutboundQueue`](Config::OutboundQueue), e.g. for
+	/// delivery to another chain or an off-chain worker.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum KittyMessage<AccountId, Hash> {
+		/// A new Kitty was minted. [owner, kitty_id, dna]
+		Created { owner: AccountId, kitty_id: Hash, dna: [u8; 16] },
+		/// A Kitty changed owner. [from, to, kitty_id]
+		Transferred { from: AccountId, to: AccountId, kitty_id: Hash },
+	}
+
+	/// A sink that Kitty lifecycle messages are relayed onto. Implement this to bridge Kitty
+	/// events to another chain or transport without coupling the pallet to any specific one.
+	pub trait KittyMessageSink<AccountId, Hash> {
+		fn enqueue(msg: KittyMessage<AccountId, Hash>);
+	}
+
+	/// A sink that drops every message. The default for runtimes that don't need a bridge.
+	impl<AccountId, Hash> KittyMessageSink<AccountId, Hash> for () {
+		fn enqueue(_msg: KittyMessage<AccountId, Hash>) {}
+	}
+
+	/// A sink that appends messages to [`OutboundMessages`], for runtimes that would rather drain
+	/// the queue themselves (e.g. from their own `on_finalize` hook) than relay eagerly.
+	pub struct StorageQueue<T>(PhantomData<T>);
+
+	impl<T: Config> KittyMessageSink<T::AccountId, T::Hash> for StorageQueue<T> {
+		fn enqueue(msg: KittyMessage<T::AccountId, T::Hash>) {
+			let _ = <OutboundMessages<T>>::try_mutate(|queue| queue.try_push(msg));
+		}
+	}
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub (super) trait Store)]
 	pub struct Pallet<T>(_);
@@ -65,6 +107,16 @@ pub mod pallet {
 		/// The maximum length of a kitty name.
 		#[pallet::constant]
 		type MaxNameLength: Get<u32>;
+
+		/// Where Created/Transferred Kitty messages are relayed to, e.g. a cross-chain bridge.
+		type OutboundQueue: KittyMessageSink<Self::AccountId, Self::Hash>;
+
+		/// The maximum number of messages the in-storage [`OutboundMessages`] queue will hold.
+		#[pallet::constant]
+		type MaxOutboundMessages: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::error]
@@ -96,6 +148,8 @@ pub mod pallet {
 		NameTooLong,
 		/// Ensure parents are of different sex
 		SameSex,
+		/// Parents are too closely related to breed.
+		TooCloselyRelated,
 	}
 
 	#[pallet::event]
@@ -137,6 +191,23 @@ pub mod pallet {
 	/// Maps Kitty Dna to Kitty Id
 	pub(super) type DnaToKitty<T: Config> = StorageMap<_, Twox64Concat, [u8; 16], T::Hash>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn owned_kitty_index)]
+	/// Maps an (owner, Kitty) pair to that Kitty's position in the owner's `KittiesOwned` vector,
+	/// so it can be removed in O(1) via `swap_remove` instead of a linear scan.
+	pub(super) type OwnedKittyIndex<T: Config> =
+		StorageMap<_, Twox64Concat, (T::AccountId, T::Hash), u32>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn outbound_messages)]
+	/// Outbound Kitty messages queued by [`StorageQueue`], waiting to be drained by
+	/// [`Pallet::drain_outbound_messages`].
+	pub(super) type OutboundMessages<T: Config> = StorageValue<
+		_,
+		BoundedVec<KittyMessage<T::AccountId, T::Hash>, T::MaxOutboundMessages>,
+		ValueQuery,
+	>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub kitties: Vec<(T::AccountId, [u8; 16], Gender)>,
@@ -155,7 +226,7 @@ pub mod pallet {
 			// When building a kitty from genesis config, we require the dna and gender to be
 			// supplied.
 			for (acct, dna, gender) in &self.kitties {
-				let _ = <Pallet<T>>::mint(acct, Some(dna.clone()), Some(gender.clone()));
+				let _ = <Pallet<T>>::mint(acct, Some(dna.clone()), Some(gender.clone()), None);
 			}
 		}
 	}
@@ -168,11 +239,11 @@ pub mod pallet {
 		/// Create a new unique kitty.
 		///
 		/// The actual kitty creation is done in the `mint()` function.
-		#[pallet::weight(100)]
+		#[pallet::weight(T::WeightInfo::create_kitty())]
 		pub fn create_kitty(origin: OriginFor<T>) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			let kitty_id = Self::mint(&sender, None, None)?;
+			let kitty_id = Self::mint(&sender, None, None, None)?;
 
 			// Deposit our "Created" event.
 			Self::deposit_event(Event::Created(sender, kitty_id));
@@ -182,7 +253,7 @@ pub mod pallet {
 		/// Set the price for a Kitty.
 		///
 		/// Updates Kitty price and updates storage.
-		#[pallet::weight(100)]
+		#[pallet::weight(T::WeightInfo::set_price())]
 		pub fn set_price(
 			origin: OriginFor<T>,
 			kitty_id: T::Hash,
@@ -208,7 +279,7 @@ pub mod pallet {
 		///
 		/// Any account that holds a kitty can send it to another Account. This will reset the
 		/// asking price of the kitty, marking it not for sale.
-		#[pallet::weight(100)]
+		#[pallet::weight(T::WeightInfo::transfer())]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			to: T::AccountId,
@@ -231,6 +302,11 @@ pub mod pallet {
 
 			Self::transfer_kitty_to(&kitty_id, &to)?;
 
+			T::OutboundQueue::enqueue(KittyMessage::Transferred {
+				from: from.clone(),
+				to: to.clone(),
+				kitty_id,
+			});
 			Self::deposit_event(Event::Transferred(from, to, kitty_id));
 
 			Ok(())
@@ -243,7 +319,7 @@ pub mod pallet {
 		/// Marking this method `transactional` so when an error is returned, we ensure no storage
 		/// is changed.
 		#[transactional]
-		#[pallet::weight(100)]
+		#[pallet::weight(T::WeightInfo::buy_kitty())]
 		pub fn buy_kitty(
 			origin: OriginFor<T>,
 			kitty_id: T::Hash,
@@ -280,6 +356,11 @@ pub mod pallet {
 			// Transfer the kitty from seller to buyer
 			Self::transfer_kitty_to(&kitty_id, &buyer)?;
 
+			T::OutboundQueue::enqueue(KittyMessage::Transferred {
+				from: seller.clone(),
+				to: buyer.clone(),
+				kitty_id,
+			});
 			Self::deposit_event(Event::Bought(buyer, seller, kitty_id, bid_price));
 
 			Ok(())
@@ -289,7 +370,7 @@ pub mod pallet {
 		///
 		/// Breed two kitties to create a new generation
 		/// of Kitties.
-		#[pallet::weight(100)]
+		#[pallet::weight(T::WeightInfo::breed_kitty())]
 		pub fn breed_kitty(
 			origin: OriginFor<T>,
 			parent1: T::Hash,
@@ -306,16 +387,25 @@ pub mod pallet {
 			ensure!(Self::is_kitty_owner(&parent1, &sender)?, <Error<T>>::NotKittyOwner);
 			ensure!(Self::is_kitty_owner(&parent2, &sender)?, <Error<T>>::NotKittyOwner);
 
-			ensure!(kitty1.unwrap().gender != kitty2.unwrap().gender, <Error<T>>::SameSex);
+			let kitty1 = kitty1.unwrap();
+			let kitty2 = kitty2.unwrap();
+
+			ensure!(kitty1.gender != kitty2.gender, <Error<T>>::SameSex);
+
+			// Check: parents must not share a parent, nor be parent and child.
+			ensure!(
+				!Self::are_too_closely_related(&parent1, &parent2, &kitty1, &kitty2),
+				<Error<T>>::TooCloselyRelated
+			);
 
 			let new_dna = Self::breed_dna(&parent1, &parent2)?;
-			Self::mint(&sender, Some(new_dna), None)?;
+			Self::mint(&sender, Some(new_dna), None, Some((parent1, parent2)))?;
 
 			Ok(())
 		}
 
 		/// Name a Kitty.
-		#[pallet::weight(100)]
+		#[pallet::weight(T::WeightInfo::name_kitty())]
 		pub fn name_kitty(
 			origin: OriginFor<T>,
 			kitty_id: T::Hash,
@@ -365,13 +455,25 @@ pub mod pallet {
 			owner: &T::AccountId,
 			dna: Option<[u8; 16]>,
 			gender: Option<Gender>,
+			parents: Option<(T::Hash, T::Hash)>,
 		) -> Result<T::Hash, Error<T>> {
+			let generation = match parents {
+				Some((parent1, parent2)) => {
+					let gen1 = Self::kitties(parent1).map(|k| k.generation).unwrap_or(0);
+					let gen2 = Self::kitties(parent2).map(|k| k.generation).unwrap_or(0);
+					gen1.max(gen2).checked_add(1).ok_or(<Error<T>>::KittyCntOverflow)?
+				},
+				None => 0,
+			};
+
 			let kitty = Kitty::<T> {
 				dna: dna.unwrap_or_else(Self::gen_dna),
 				price: None,
 				gender: gender.unwrap_or_else(Self::gen_gender),
 				owner: owner.clone(),
 				name: None,
+				generation,
+				parents,
 			};
 
 			let kitty_id = T::Hashing::hash_of(&kitty);
@@ -383,15 +485,73 @@ pub mod pallet {
 			ensure!(Self::kitties(&kitty_id).is_none(), <Error<T>>::KittyExists);
 
 			// Performs this operation first because as it may fail
-			<KittiesOwned<T>>::try_mutate(&owner, |kitty_vec| kitty_vec.try_push(kitty_id))
-				.map_err(|_| <Error<T>>::ExceedMaxKittyOwned)?;
+			let index = <KittiesOwned<T>>::try_mutate(&owner, |kitty_vec| {
+				kitty_vec.try_push(kitty_id).map(|()| (kitty_vec.len() - 1) as u32)
+			})
+			.map_err(|_| <Error<T>>::ExceedMaxKittyOwned)?;
+			<OwnedKittyIndex<T>>::insert((owner.clone(), kitty_id), index);
 
 			<DnaToKitty<T>>::insert(&kitty.dna, &kitty_id);
+			T::OutboundQueue::enqueue(KittyMessage::Created {
+				owner: owner.clone(),
+				kitty_id,
+				dna: kitty.dna,
+			});
 			<Kitties<T>>::insert(kitty_id, kitty);
 			<KittyCnt<T>>::put(new_cnt);
 			Ok(kitty_id)
 		}
 
+		// Check whether two kitties share a parent, or whether one is the other's direct parent.
+		fn are_too_closely_related(
+			parent1: &T::Hash,
+			parent2: &T::Hash,
+			kitty1: &Kitty<T>,
+			kitty2: &Kitty<T>,
+		) -> bool {
+			if let Some((p1, p2)) = kitty1.parents {
+				if p1 == *parent2 || p2 == *parent2 {
+					return true
+				}
+			}
+			if let Some((p1, p2)) = kitty2.parents {
+				if p1 == *parent1 || p2 == *parent1 {
+					return true
+				}
+			}
+			match (kitty1.parents, kitty2.parents) {
+				(Some((a1, b1)), Some((a2, b2))) => a1 == a2 || a1 == b2 || b1 == a2 || b1 == b2,
+				_ => false,
+			}
+		}
+
+		/// Walk the `parents` links of `kitty_id` up to `depth` generations, returning every
+		/// ancestor found along the way. Intended for front-ends that want to render a family tree.
+		pub fn ancestors(kitty_id: T::Hash, depth: u32) -> Vec<T::Hash> {
+			let mut result = Vec::new();
+			let mut frontier = vec![kitty_id];
+
+			for _ in 0..depth {
+				let mut next_frontier = Vec::new();
+				for id in frontier {
+					if let Some((parent1, parent2)) =
+						Self::kitties(id).and_then(|kitty| kitty.parents)
+					{
+						result.push(parent1);
+						result.push(parent2);
+						next_frontier.push(parent1);
+						next_frontier.push(parent2);
+					}
+				}
+				if next_frontier.is_empty() {
+					break
+				}
+				frontier = next_frontier;
+			}
+
+			result
+		}
+
 		pub fn is_kitty_owner(kitty_id: &T::Hash, acct: &T::AccountId) -> Result<bool, Error<T>> {
 			match Self::kitties(kitty_id) {
 				Some(kitty) => Ok(kitty.owner == *acct),
@@ -405,15 +565,25 @@ pub mod pallet {
 
 			let prev_owner = kitty.owner.clone();
 
-			// Remove `kitty_id` from the KittyOwned vector of `prev_kitty_owner`
+			// Remove `kitty_id` from the KittyOwned vector of `prev_kitty_owner`, via its indexed
+			// position so the removal is O(1) instead of a linear scan.
 			<KittiesOwned<T>>::try_mutate(&prev_owner, |owned| {
-				if let Some(ind) = owned.iter().position(|&id| id == *kitty_id) {
-					owned.swap_remove(ind);
-					return Ok(())
+				let index =
+					<OwnedKittyIndex<T>>::get((prev_owner.clone(), *kitty_id)).ok_or(())? as usize;
+				ensure!(index < owned.len() && owned[index] == *kitty_id, ());
+
+				owned.swap_remove(index);
+				<OwnedKittyIndex<T>>::remove((prev_owner.clone(), *kitty_id));
+
+				// The element that used to be last now sits at `index`; point its index entry at
+				// its new position.
+				if let Some(&moved_id) = owned.get(index) {
+					<OwnedKittyIndex<T>>::insert((prev_owner.clone(), moved_id), index as u32);
 				}
-				Err(())
+
+				Ok(())
 			})
-			.map_err(|_| <Error<T>>::KittyNotExist)?;
+			.map_err(|_: ()| <Error<T>>::KittyNotExist)?;
 
 			// Update the kitty owner
 			kitty.owner = to.clone();
@@ -423,12 +593,22 @@ pub mod pallet {
 
 			<Kitties<T>>::insert(kitty_id, kitty);
 
-			<KittiesOwned<T>>::try_mutate(to, |vec| vec.try_push(*kitty_id))
-				.map_err(|_| <Error<T>>::ExceedMaxKittyOwned)?;
+			let new_index = <KittiesOwned<T>>::try_mutate(to, |vec| {
+				vec.try_push(*kitty_id).map(|()| (vec.len() - 1) as u32)
+			})
+			.map_err(|_| <Error<T>>::ExceedMaxKittyOwned)?;
+			<OwnedKittyIndex<T>>::insert((to.clone(), *kitty_id), new_index);
 
 			Ok(())
 		}
 
+		/// Take every message out of [`OutboundMessages`], leaving it empty. Intended to be called
+		/// from a runtime's own `on_finalize` hook when using [`StorageQueue`] as the
+		/// `OutboundQueue`.
+		pub fn drain_outbound_messages() -> Vec<KittyMessage<T::AccountId, T::Hash>> {
+			<OutboundMessages<T>>::take().into_inner()
+		}
+
 		pub fn fetch_kitty_id(dna: [u8; 16]) -> Option<T::Hash> {
 			if let Some(kitty_id) = Self::dna_to_kitty(dna) {
 				return Some(kitty_id);
@@ -0,0 +1,124 @@
+//! Autogenerated weights for pallet_kitties
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2026-07-27, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Wasm, WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+// Executed Command:
+// ./target/release/node-template
+// benchmark
+// --chain=dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet=pallet_kitties
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --output=./pallets/kitties/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_kitties.
+pub trait WeightInfo {
+	fn create_kitty() -> Weight;
+	fn set_price() -> Weight;
+	fn transfer() -> Weight;
+	fn buy_kitty() -> Weight;
+	fn breed_kitty() -> Weight;
+	fn name_kitty() -> Weight;
+}
+
+/// Weights for pallet_kitties using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Kitties KittyCnt (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:1 w:1)
+	// Storage: Kitties DnaToKitty (r:0 w:1)
+	// Storage: Kitties Kitties (r:1 w:1)
+	// Storage: Kitties OwnedKittyIndex (r:0 w:1)
+	fn create_kitty() -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Kitties Kitties (r:1 w:1)
+	fn set_price() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Kitties Kitties (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:2 w:2)
+	// Storage: Kitties OwnedKittyIndex (r:1 w:3)
+	fn transfer() -> Weight {
+		(39_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Kitties Kitties (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:2 w:2)
+	// Storage: Kitties OwnedKittyIndex (r:1 w:2)
+	fn buy_kitty() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Kitties Kitties (r:3 w:1)
+	// Storage: Kitties KittyCnt (r:1 w:1)
+	// Storage: Kitties KittiesOwned (r:1 w:1)
+	// Storage: Kitties DnaToKitty (r:0 w:1)
+	// Storage: Kitties OwnedKittyIndex (r:0 w:1)
+	fn breed_kitty() -> Weight {
+		(61_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Kitties Kitties (r:1 w:1)
+	fn name_kitty() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn create_kitty() -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn set_price() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn transfer() -> Weight {
+		(39_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn buy_kitty() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn breed_kitty() -> Weight {
+		(61_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn name_kitty() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}